@@ -0,0 +1,46 @@
+//! Unicode confusable/homoglyph detection for the lexer's error path.
+//!
+//! When `tokenize` hits a character it doesn't recognize, a surprising
+//! fraction of the time it's a Unicode look-alike standing in for an ASCII
+//! punctuation character the author meant to type -- a fullwidth semicolon,
+//! a Greek question mark where a semicolon belongs, curly quotes instead of
+//! straight ones, or a non-breaking space masquerading as whitespace. This
+//! table maps those code points back to the ASCII token they were probably
+//! meant to be, so the lexer can say "did you mean ';'?" instead of just
+//! failing on an opaque code point.
+
+/// Returns the ASCII character a known Unicode confusable was probably
+/// meant to stand in for, or `None` if `c` isn't a recognized confusable.
+pub fn lookup_confusable(c: char) -> Option<char> {
+    match c {
+        '\u{FF1B}' | '\u{037E}' => Some(';'), // fullwidth semicolon, Greek question mark
+        '\u{FF0C}' => Some(','),              // fullwidth comma
+        '\u{FF08}' => Some('('),              // fullwidth left parenthesis
+        '\u{FF09}' => Some(')'),              // fullwidth right parenthesis
+        '\u{FF5B}' => Some('{'),              // fullwidth left curly bracket
+        '\u{FF5D}' => Some('}'),              // fullwidth right curly bracket
+        '\u{201C}' | '\u{201D}' => Some('"'),  // left/right double quotation mark
+        '\u{2018}' | '\u{2019}' => Some('\''), // left/right single quotation mark
+        '\u{00A0}' | '\u{2007}' | '\u{202F}' => Some(' '), // no-break spaces
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_confusable() {
+        assert_eq!(lookup_confusable('\u{FF1B}'), Some(';'));
+        assert_eq!(lookup_confusable('\u{037E}'), Some(';'));
+        assert_eq!(lookup_confusable('\u{201C}'), Some('"'));
+        assert_eq!(lookup_confusable('\u{00A0}'), Some(' '));
+    }
+
+    #[test]
+    fn test_lookup_confusable_none_for_ordinary_chars() {
+        assert_eq!(lookup_confusable('a'), None);
+        assert_eq!(lookup_confusable(';'), None);
+    }
+}
@@ -4,10 +4,10 @@ use serde::Serialize;
 use self::{
     code_iter::{current_span_error, IntoCodeIterator, Span},
     comment::Comment,
-    ident::{IdentParseResult, Identifier, Keyword, ValueLiteral},
+    ident::{IdentParseResult, Identifier, Keyword, KeywordType, ValueLiteral},
     num::NumberLiteral,
-    operator::Operator,
-    punctuation::Punctuation,
+    operator::{Operator, OperatorType},
+    punctuation::{Punctuation, PunctuationType},
     regex::RegexLiteral,
     string::StringLiteral,
     template::{TemplateLiteralExprClose, TemplateLiteralExprOpen, TemplateLiteralString},
@@ -15,6 +15,7 @@ use self::{
 
 mod code_iter;
 mod comment;
+mod confusables;
 mod escape_chars;
 mod ident;
 mod num;
@@ -40,26 +41,143 @@ pub enum Token {
     TemplateLiteralExprOpen(TemplateLiteralExprOpen),
     TemplateLiteralExprClose(TemplateLiteralExprClose),
     RegexLiteral(RegexLiteral),
+    /// Sentinel token pushed as the last element of every token stream,
+    /// carrying the span just past the final character of the source. Lets
+    /// a parser treat "ran out of tokens" the same way as any other token
+    /// lookahead, instead of special-casing an empty `Option`.
+    Eof(Span),
 }
 
-pub fn tokenize(src: &str, file_name: impl Into<String>) -> Result<Vec<Token>> {
+/// A [`Token`] paired with the source range it was lexed from, so that a
+/// parser or diagnostic renderer can point at exact ranges instead of
+/// re-deriving positions from the token stream.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct Spanned<T> {
+    pub start: Span,
+    pub end: Span,
+    pub token: T,
+}
+
+impl<T> Spanned<T> {
+    fn new(start: Span, end: Span, token: T) -> Self {
+        Self { start, end, token }
+    }
+}
+
+/// The lexer goal symbol that disambiguates a leading `/` as the start of a
+/// regex literal or as the division operator, per the ECMAScript lexical
+/// grammar. `tokenize` maintains this from the preceding token and takes no
+/// goal symbol from its caller, so it cannot be overridden for a whole token
+/// stream. [`try_parse_regex_literal`] takes the goal symbol as an explicit
+/// `goal` argument, but the `regex` module is private, so only code inside
+/// the `lexer` module can call it.
+///
+/// [`try_parse_regex_literal`]: regex::try_parse_regex_literal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum GoalSymbol {
+    ExpectRegex,
+    ExpectDivision,
+}
+
+/// Computes the goal symbol that should be in effect for the token *after*
+/// `token`, given whether a just-closed `)` terminated an `if`/`for`/`while`
+/// head (in which case a regex may legally follow, e.g. `if (x) /re/.test(x)`),
+/// and whether a just-closed `}` closed a statement block rather than an
+/// object literal (e.g. `if (x) { y; } /re/` vs `({}) / 2`).
+fn next_goal_symbol(
+    token: &Token,
+    closed_control_flow_paren: bool,
+    closed_block_brace: bool,
+) -> GoalSymbol {
+    match token {
+        Token::Punctuation(p) if p.kind == PunctuationType::CloseParen => {
+            if closed_control_flow_paren {
+                GoalSymbol::ExpectRegex
+            } else {
+                GoalSymbol::ExpectDivision
+            }
+        }
+        Token::Punctuation(p) if p.kind == PunctuationType::CloseBrace => {
+            if closed_block_brace {
+                GoalSymbol::ExpectRegex
+            } else {
+                GoalSymbol::ExpectDivision
+            }
+        }
+        Token::Ident(_)
+        | Token::ValueLiteral(_)
+        | Token::NumericLiteral(_)
+        | Token::StringLiteral(_)
+        | Token::RegexLiteral(_)
+        | Token::TemplateLiteralString(_) => GoalSymbol::ExpectDivision,
+        Token::Operator(op)
+            if matches!(op.kind, OperatorType::Increment | OperatorType::Decrement) =>
+        {
+            GoalSymbol::ExpectDivision
+        }
+        _ => GoalSymbol::ExpectRegex,
+    }
+}
+
+pub fn tokenize(src: &str, file_name: impl Into<String>) -> Result<Vec<Spanned<Token>>> {
     let mut chars = src.into_code_iterator(file_name.into());
-    let mut tokens = Vec::<Token>::new();
-    let mut template_depth = 0;
+    let mut tokens = Vec::<Spanned<Token>>::new();
+    // One frame per currently-open `${ }` substitution, tracking how many
+    // braces have been opened (by object literals, blocks, etc.) inside that
+    // substitution; a nested `${` pushes a frame of its own rather than
+    // counting towards the enclosing one. A `}` only closes the substitution
+    // itself when the top frame's depth is zero; otherwise it balances a
+    // brace nested inside the substitution's expression.
+    let mut template_frames: Vec<usize> = Vec::new();
+    // Whether each currently-open `(` is the head of an `if`/`for`/`while`,
+    // so that its matching `)` can set the goal symbol back to
+    // `ExpectRegex` (e.g. `if (x) /re/.test(x)`).
+    let mut paren_is_control_flow: Vec<bool> = Vec::new();
+    let mut pending_control_flow_paren = false;
+    // Whether each currently-open `{` is a statement block rather than an
+    // object literal, determined from the token immediately preceding it
+    // (e.g. a `)`, `;`, `{`, `}` or keyword almost always precedes a block;
+    // anything else -- `=`, `(`, `,`, `return`, an operator -- is a value
+    // position, so the brace is an object literal).
+    let mut brace_is_block: Vec<bool> = Vec::new();
+    let mut goal_symbol = GoalSymbol::ExpectRegex;
 
     'outer: loop {
+        let token_start = chars.current_position();
+
         if chars.peek().is_none() {
+            tokens.push(Spanned::new(token_start, token_start, Token::Eof(token_start)));
             break;
         }
 
         if tokens.is_empty() {
             if let Some(comment) = comment::try_parse_hashbang_comment(&mut chars) {
-                tokens.push(Token::Comment(comment));
+                tokens.push(Spanned::new(
+                    token_start,
+                    chars.current_position(),
+                    Token::Comment(comment),
+                ));
                 continue 'outer;
             }
         }
 
-        if let Some(next_char) = chars.peek() {
+        if let Some(next_char) = chars.peek().copied() {
+            // Checked ahead of the whitespace skip below: some confusables
+            // (e.g. no-break spaces) are themselves whitespace by Unicode's
+            // definition, so if this ran after the skip it would silently
+            // swallow them and the "did you mean ' '?" diagnostic could
+            // never fire.
+            if let Some(suggestion) = confusables::lookup_confusable(next_char) {
+                return Err(current_span_error!(
+                    chars,
+                    chars.current_position(),
+                    "Unrecognized character '{}' (U+{:04X}) -- did you mean '{}'?",
+                    next_char,
+                    next_char as u32,
+                    suggestion
+                ));
+            }
+
             if next_char.is_whitespace() {
                 chars.next();
                 continue 'outer;
@@ -67,82 +185,202 @@ pub fn tokenize(src: &str, file_name: impl Into<String>) -> Result<Vec<Token>> {
         }
 
         if let Some(comment) = comment::try_parse_comment(&mut chars) {
-            tokens.push(Token::Comment(comment));
+            tokens.push(Spanned::new(
+                token_start,
+                chars.current_position(),
+                Token::Comment(comment),
+            ));
             continue 'outer;
         }
 
         if let Some((template_content, template_expr_open)) =
             template::try_parse_template_literal_start(&mut chars)?
         {
-            template_depth += 1;
-            tokens.push(Token::TemplateLiteralString(template_content));
+            pending_control_flow_paren = false;
+            goal_symbol = GoalSymbol::ExpectDivision;
+            tokens.push(Spanned::new(
+                token_start,
+                chars.current_position(),
+                Token::TemplateLiteralString(template_content),
+            ));
 
             if let Some(template_expr_open) = template_expr_open {
-                tokens.push(Token::TemplateLiteralExprOpen(template_expr_open));
+                template_frames.push(0);
+                goal_symbol = GoalSymbol::ExpectRegex;
+                tokens.push(Spanned::new(
+                    chars.current_position(),
+                    chars.current_position(),
+                    Token::TemplateLiteralExprOpen(template_expr_open),
+                ));
             }
 
             continue 'outer;
         }
 
         if let Some(parse_result) = ident::try_parse_identifier(&mut chars)? {
-            match parse_result {
-                IdentParseResult::Identifier(ident) => {
-                    tokens.push(Token::Ident(ident));
-                }
-                IdentParseResult::Keyword(keyword) => {
-                    tokens.push(Token::Keyword(keyword));
-                }
+            let token_end = chars.current_position();
+            let token = match parse_result {
+                IdentParseResult::Identifier(ident) => Token::Ident(ident),
+                IdentParseResult::Keyword(keyword) => Token::Keyword(keyword),
                 IdentParseResult::ValueLiteral(value_literal) => {
-                    tokens.push(Token::ValueLiteral(value_literal));
+                    Token::ValueLiteral(value_literal)
                 }
-                IdentParseResult::Operator(operator) => {
-                    tokens.push(Token::Operator(operator));
-                }
-            }
+                IdentParseResult::Operator(operator) => Token::Operator(operator),
+            };
+
+            pending_control_flow_paren = matches!(
+                &token,
+                Token::Keyword(kw)
+                    if matches!(kw.kind, KeywordType::If | KeywordType::For | KeywordType::While)
+            );
+            goal_symbol = next_goal_symbol(&token, false, false);
+            tokens.push(Spanned::new(token_start, token_end, token));
 
             continue 'outer;
         }
 
-        if template_depth > 0 {
+        // Only attempt to close the innermost substitution when its frame
+        // has no unbalanced braces open; otherwise this `}` belongs to a
+        // nested object literal/block and must fall through to regular
+        // punctuation lexing below.
+        if template_frames.last() == Some(&0) {
             if let Some((expr_close, template_content, expr_open)) =
                 template::try_parse_template_literal_expr_end(&mut chars)?
             {
-                template_depth -= 1;
-                tokens.push(Token::TemplateLiteralExprClose(expr_close));
-                tokens.push(Token::TemplateLiteralString(template_content));
+                template_frames.pop();
+                pending_control_flow_paren = false;
+                goal_symbol = GoalSymbol::ExpectDivision;
+                // `try_parse_template_literal_expr_end` consumes the closing
+                // `}`, the trailing literal text, and a possible following
+                // `${` in one call, so we only ever observe the position
+                // before and after all of it -- there's no way to recover
+                // the exact byte where the `}` ends and the literal begins.
+                // Rather than attribute that whole range to the close token
+                // and collapse the literal's span to a single point past the
+                // end (which points diagnostics at the wrong token entirely),
+                // give both tokens the same honest, if imprecise, range.
+                let substitution_end = chars.current_position();
+                tokens.push(Spanned::new(
+                    token_start,
+                    substitution_end,
+                    Token::TemplateLiteralExprClose(expr_close),
+                ));
+                tokens.push(Spanned::new(
+                    token_start,
+                    substitution_end,
+                    Token::TemplateLiteralString(template_content),
+                ));
 
                 if let Some(expr_open) = expr_open {
-                    template_depth += 1;
-                    tokens.push(Token::TemplateLiteralExprOpen(expr_open));
+                    template_frames.push(0);
+                    goal_symbol = GoalSymbol::ExpectRegex;
+                    tokens.push(Spanned::new(
+                        chars.current_position(),
+                        chars.current_position(),
+                        Token::TemplateLiteralExprOpen(expr_open),
+                    ));
                 }
 
                 continue 'outer;
             }
         }
 
-        if let Some(regexp) = regex::try_parse_regex_literal(&mut chars, tokens.last())? {
-            tokens.push(Token::RegexLiteral(regexp));
+        if let Some(regexp) = regex::try_parse_regex_literal(&mut chars, goal_symbol)? {
+            pending_control_flow_paren = false;
+            goal_symbol = GoalSymbol::ExpectDivision;
+            tokens.push(Spanned::new(
+                token_start,
+                chars.current_position(),
+                Token::RegexLiteral(regexp),
+            ));
             continue 'outer;
         }
 
         if let Some(string_literal) = string::try_parse_string(&mut chars)? {
-            tokens.push(Token::StringLiteral(string_literal));
+            pending_control_flow_paren = false;
+            goal_symbol = GoalSymbol::ExpectDivision;
+            tokens.push(Spanned::new(
+                token_start,
+                chars.current_position(),
+                Token::StringLiteral(string_literal),
+            ));
             continue 'outer;
         }
 
         if let Some(number_value) = num::try_parse_number(&mut chars)? {
-            tokens.push(Token::NumericLiteral(NumberLiteral::new(number_value)));
+            pending_control_flow_paren = false;
+            goal_symbol = GoalSymbol::ExpectDivision;
+            tokens.push(Spanned::new(
+                token_start,
+                chars.current_position(),
+                Token::NumericLiteral(NumberLiteral::new(number_value)),
+            ));
 
             continue 'outer;
         }
 
         if let Some(punctuation) = punctuation::try_parse_punctuation(&mut chars) {
-            tokens.push(Token::Punctuation(punctuation));
+            if let Some(frame_depth) = template_frames.last_mut() {
+                match punctuation.kind {
+                    PunctuationType::OpenBrace => *frame_depth += 1,
+                    PunctuationType::CloseBrace => *frame_depth = frame_depth.saturating_sub(1),
+                    _ => {}
+                }
+            }
+
+            let mut closed_control_flow_paren = false;
+            let mut closed_block_brace = false;
+            match punctuation.kind {
+                PunctuationType::OpenParen => {
+                    paren_is_control_flow.push(pending_control_flow_paren);
+                    pending_control_flow_paren = false;
+                }
+                PunctuationType::CloseParen => {
+                    closed_control_flow_paren = paren_is_control_flow.pop().unwrap_or(false);
+                }
+                PunctuationType::OpenBrace => {
+                    let is_block = match tokens.last().map(|s| &s.token) {
+                        None => true,
+                        Some(Token::Punctuation(p)) => matches!(
+                            p.kind,
+                            PunctuationType::CloseParen
+                                | PunctuationType::Semicolon
+                                | PunctuationType::OpenBrace
+                                | PunctuationType::CloseBrace
+                        ),
+                        // Most keywords precede a block (`else {`, `do {`,
+                        // `try {`, `finally {`, ...), but a handful expect a
+                        // value and make the brace an object literal instead.
+                        Some(Token::Keyword(kw)) => !matches!(
+                            kw.kind,
+                            KeywordType::Return
+                                | KeywordType::Typeof
+                                | KeywordType::Yield
+                                | KeywordType::Delete
+                        ),
+                        _ => false,
+                    };
+                    brace_is_block.push(is_block);
+                    pending_control_flow_paren = false;
+                }
+                PunctuationType::CloseBrace => {
+                    closed_block_brace = brace_is_block.pop().unwrap_or(true);
+                    pending_control_flow_paren = false;
+                }
+                _ => pending_control_flow_paren = false,
+            }
+
+            let token = Token::Punctuation(punctuation);
+            goal_symbol = next_goal_symbol(&token, closed_control_flow_paren, closed_block_brace);
+            tokens.push(Spanned::new(token_start, chars.current_position(), token));
             continue 'outer;
         }
 
         if let Some(operator) = operator::try_parse_operator(&mut chars) {
-            tokens.push(Token::Operator(operator));
+            pending_control_flow_paren = false;
+            let token = Token::Operator(operator);
+            goal_symbol = next_goal_symbol(&token, false, false);
+            tokens.push(Spanned::new(token_start, chars.current_position(), token));
             continue 'outer;
         }
 
@@ -172,8 +410,17 @@ mod tests {
         let src = r#"
             (1) / 2
         "#;
+        let spanned = tokenize(src, "script.js").unwrap();
+        assert!(matches!(
+            spanned.last(),
+            Some(Spanned {
+                token: Token::Eof(_),
+                ..
+            })
+        ));
+        let tokens: Vec<Token> = spanned.into_iter().map(|s| s.token).collect();
         assert_eq!(
-            tokenize(src, "script.js").unwrap(),
+            &tokens[..tokens.len() - 1],
             vec![
                 Token::Punctuation(Punctuation::new(PunctuationType::OpenParen)),
                 Token::NumericLiteral(NumberLiteral::new(NumberLiteralValue::Primitive(1.0))),
@@ -184,6 +431,183 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_goal_symbol_return_then_regex() {
+        let src = "return /re/;";
+        let spanned = tokenize(src, "script.js").unwrap();
+        let tokens: Vec<Token> = spanned.into_iter().map(|s| s.token).collect();
+        assert_eq!(
+            &tokens[..tokens.len() - 1],
+            vec![
+                Token::Keyword(Keyword::new("return".try_into().unwrap())),
+                Token::RegexLiteral(RegexLiteral::new("re".into(), "".into())),
+                Token::Punctuation(Punctuation::new(PunctuationType::Semicolon)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_goal_symbol_block_close_then_regex() {
+        let src = "if (x) { y; } /z/";
+        let spanned = tokenize(src, "script.js").unwrap();
+        let tokens: Vec<Token> = spanned.into_iter().map(|s| s.token).collect();
+        assert_eq!(
+            &tokens[..tokens.len() - 1],
+            vec![
+                Token::Keyword(Keyword::new("if".try_into().unwrap())),
+                Token::Punctuation(Punctuation::new(PunctuationType::OpenParen)),
+                Token::Ident("x".into()),
+                Token::Punctuation(Punctuation::new(PunctuationType::CloseParen)),
+                Token::Punctuation(Punctuation::new(PunctuationType::OpenBrace)),
+                Token::Ident("y".into()),
+                Token::Punctuation(Punctuation::new(PunctuationType::Semicolon)),
+                Token::Punctuation(Punctuation::new(PunctuationType::CloseBrace)),
+                Token::RegexLiteral(RegexLiteral::new("z".into(), "".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_goal_symbol_object_literal_close_then_division() {
+        let src = "x = {} / 2";
+        let spanned = tokenize(src, "script.js").unwrap();
+        let tokens: Vec<Token> = spanned.into_iter().map(|s| s.token).collect();
+        assert_eq!(
+            &tokens[..tokens.len() - 1],
+            vec![
+                Token::Ident("x".into()),
+                Token::Operator(Operator::new(OperatorType::Assignment)),
+                Token::Punctuation(Punctuation::new(PunctuationType::OpenBrace)),
+                Token::Punctuation(Punctuation::new(PunctuationType::CloseBrace)),
+                Token::Operator(Operator::new(OperatorType::Division)),
+                Token::NumericLiteral(NumberLiteral::new(NumberLiteralValue::Primitive(2.0))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_goal_symbol_postfix_increment_then_division() {
+        let src = "x++ / 2";
+        let spanned = tokenize(src, "script.js").unwrap();
+        let tokens: Vec<Token> = spanned.into_iter().map(|s| s.token).collect();
+        assert_eq!(
+            &tokens[..tokens.len() - 1],
+            vec![
+                Token::Ident("x".into()),
+                Token::Operator(Operator::new(OperatorType::Increment)),
+                Token::Operator(Operator::new(OperatorType::Division)),
+                Token::NumericLiteral(NumberLiteral::new(NumberLiteralValue::Primitive(2.0))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_goal_symbol_return_of_object_literal_then_division() {
+        // `return` expects a value, so the brace it's immediately followed
+        // by is an object literal, not a block -- unlike `if (x) { ... }`,
+        // a `/` right after its matching `}` is division, not a regex.
+        let src = "return {} / 2;";
+        let spanned = tokenize(src, "script.js").unwrap();
+        let tokens: Vec<Token> = spanned.into_iter().map(|s| s.token).collect();
+        assert_eq!(
+            &tokens[..tokens.len() - 1],
+            vec![
+                Token::Keyword(Keyword::new("return".try_into().unwrap())),
+                Token::Punctuation(Punctuation::new(PunctuationType::OpenBrace)),
+                Token::Punctuation(Punctuation::new(PunctuationType::CloseBrace)),
+                Token::Operator(Operator::new(OperatorType::Division)),
+                Token::NumericLiteral(NumberLiteral::new(NumberLiteralValue::Primitive(2.0))),
+                Token::Punctuation(Punctuation::new(PunctuationType::Semicolon)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_template_substitution_with_object_literal_brace() {
+        // The `{` and `}` around `x` belong to an object literal nested
+        // inside the substitution, not the substitution's own closing brace
+        // -- the brace-balancing stack must tell them apart.
+        let src = "`a${ {x} }b`";
+        let spanned = tokenize(src, "script.js").unwrap();
+        let tokens: Vec<Token> = spanned.into_iter().map(|s| s.token).collect();
+        assert_eq!(
+            &tokens[..tokens.len() - 1],
+            vec![
+                Token::TemplateLiteralString(TemplateLiteralString::new("a".into(), false)),
+                Token::TemplateLiteralExprOpen(TemplateLiteralExprOpen::default()),
+                Token::Punctuation(Punctuation::new(PunctuationType::OpenBrace)),
+                Token::Ident("x".into()),
+                Token::Punctuation(Punctuation::new(PunctuationType::CloseBrace)),
+                Token::TemplateLiteralExprClose(TemplateLiteralExprClose::default()),
+                Token::TemplateLiteralString(TemplateLiteralString::new("b".into(), true)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_template_substitution_with_nested_template() {
+        // A whole nested backtick template lives inside the outer
+        // substitution; its own `${ }` must not be mistaken for the outer
+        // substitution's close.
+        let src = "`a${ `b${c}` }d`";
+        let spanned = tokenize(src, "script.js").unwrap();
+        let tokens: Vec<Token> = spanned.into_iter().map(|s| s.token).collect();
+        assert_eq!(
+            &tokens[..tokens.len() - 1],
+            vec![
+                Token::TemplateLiteralString(TemplateLiteralString::new("a".into(), false)),
+                Token::TemplateLiteralExprOpen(TemplateLiteralExprOpen::default()),
+                Token::TemplateLiteralString(TemplateLiteralString::new("b".into(), false)),
+                Token::TemplateLiteralExprOpen(TemplateLiteralExprOpen::default()),
+                Token::Ident("c".into()),
+                Token::TemplateLiteralExprClose(TemplateLiteralExprClose::default()),
+                Token::TemplateLiteralString(TemplateLiteralString::new("".into(), true)),
+                Token::TemplateLiteralExprClose(TemplateLiteralExprClose::default()),
+                Token::TemplateLiteralString(TemplateLiteralString::new("d".into(), true)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_template_expr_close_span_covers_trailing_literal() {
+        let src = "`a${b} tail`";
+        let spanned = tokenize(src, "script.js").unwrap();
+
+        let expr_close = spanned
+            .iter()
+            .find(|s| matches!(s.token, Token::TemplateLiteralExprClose(_)))
+            .unwrap();
+        let tail_literal = spanned
+            .iter()
+            .find(|s| matches!(&s.token, Token::TemplateLiteralString(_) if s.start != s.end && s.start == expr_close.start))
+            .unwrap();
+
+        // Both tokens share the same non-zero-width range -- the lexer
+        // cannot see the boundary between the closing `}` and the trailing
+        // literal text, so the literal's span must not collapse to a single
+        // point while the close token silently swallows the whole range.
+        assert_ne!(expr_close.start, expr_close.end);
+        assert_eq!(expr_close.start, tail_literal.start);
+        assert_eq!(expr_close.end, tail_literal.end);
+    }
+
+    #[test]
+    fn test_confusable_semicolon_suggests_ascii_equivalent() {
+        let err = tokenize("let x = 1\u{FF1B}", "script.js").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("did you mean ';'"), "{message}");
+    }
+
+    #[test]
+    fn test_confusable_no_break_space_is_not_silently_skipped_as_whitespace() {
+        // A no-break space is whitespace by Unicode's definition, so it must
+        // be checked for confusability *before* the lexer's whitespace skip,
+        // or this diagnostic could never fire.
+        let err = tokenize("let x\u{00A0}= 1;", "script.js").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("did you mean ' '"), "{message}");
+    }
+
     #[test]
     fn test_file_tokenization() {
         let src = r#"
@@ -195,8 +619,17 @@ export function foo() {
 }
 "#;
 
+        let spanned = tokenize(src, "script.js").unwrap();
+        assert!(matches!(
+            spanned.last(),
+            Some(Spanned {
+                token: Token::Eof(_),
+                ..
+            })
+        ));
+        let tokens: Vec<Token> = spanned.into_iter().map(|s| s.token).collect();
         assert_eq!(
-            tokenize(src, "script.js").unwrap(),
+            &tokens[..tokens.len() - 1],
             vec![
                 Token::Comment(Comment::new(CommentType::Line(
                     " This is a a comment".to_string()
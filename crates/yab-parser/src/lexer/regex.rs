@@ -2,10 +2,9 @@ use miette::Result;
 use serde::Serialize;
 
 use super::{
-    code_iter::{current_span_error, previous_span_error, CodeIter, Span},
-    punctuation::{Punctuation, PunctuationType},
+    code_iter::{current_span_error, previous_span_error, CodeIter},
     utils::is_line_terminator,
-    Token,
+    GoalSymbol,
 };
 
 /// Represents a regex literal token.  Since we're not actually parsing the
@@ -95,23 +94,15 @@ fn parse_regex_flags(chars: &mut CodeIter) -> Result<String> {
 /// lexer tries to parse comments higher up in the loop.
 pub fn try_parse_regex_literal(
     chars: &mut CodeIter,
-    previous_token: Option<&Token>,
+    goal: GoalSymbol,
 ) -> Result<Option<RegexLiteral>> {
-    // regex literals can only appear in expression contexts, so if the previous
-    // token was not a operator or punctuator we can safely assume that we must
-    // parse as division or something else.
-
-    match previous_token {
-        Some(Token::Operator(_))
-        | Some(Token::Punctuation(Punctuation {
-            kind: PunctuationType::Comma,
-            ..
-        }))
-        | Some(Token::Punctuation(Punctuation {
-            kind: PunctuationType::OpenParen,
-            ..
-        }))
-        | None => match chars.peek() {
+    // Regex literals can only appear where the lexer's goal symbol is
+    // `ExpectRegex` (e.g. after an operator, `return`, or an `if`/`for`/
+    // `while` head) -- a caller that knows the grammatical context more
+    // precisely than the lexer's own heuristic can force this goal symbol
+    // rather than relying on it.
+    match goal {
+        GoalSymbol::ExpectRegex => match chars.peek() {
             Some('/') => {
                 _ = chars.next();
                 let pattern = parse_regex_pattern(chars)?;
@@ -121,7 +112,7 @@ pub fn try_parse_regex_literal(
             }
             _ => Ok(None),
         },
-        _ => Ok(None),
+        GoalSymbol::ExpectDivision => Ok(None),
     }
 }
 
@@ -134,7 +125,9 @@ mod tests {
     #[test]
     fn test_try_parse_regex_literal() {
         let mut chars = "/foo/g".into_code_iterator("script.js".to_string());
-        let result = try_parse_regex_literal(&mut chars, None).unwrap().unwrap();
+        let result = try_parse_regex_literal(&mut chars, GoalSymbol::ExpectRegex)
+            .unwrap()
+            .unwrap();
         assert_eq!(
             result,
             RegexLiteral {
@@ -147,7 +140,9 @@ mod tests {
     #[test]
     fn test_regex_without_flags() {
         let mut chars = "/foo/".into_code_iterator("script.js".to_string());
-        let result = try_parse_regex_literal(&mut chars, None).unwrap().unwrap();
+        let result = try_parse_regex_literal(&mut chars, GoalSymbol::ExpectRegex)
+            .unwrap()
+            .unwrap();
         assert_eq!(
             result,
             RegexLiteral {
@@ -160,7 +155,7 @@ mod tests {
     #[test]
     fn test_regex_with_invalid_flags() {
         let mut chars = "/foo/z".into_code_iterator("script.js".to_string());
-        let result = try_parse_regex_literal(&mut chars, None);
+        let result = try_parse_regex_literal(&mut chars, GoalSymbol::ExpectRegex);
 
         assert!(result
             .unwrap_err()
@@ -171,7 +166,7 @@ mod tests {
     #[test]
     fn test_regex_with_unexpected_line_break() {
         let mut chars = "/foo\n/z".into_code_iterator("script.js".to_string());
-        let result = try_parse_regex_literal(&mut chars, None);
+        let result = try_parse_regex_literal(&mut chars, GoalSymbol::ExpectRegex);
 
         assert!(result
             .unwrap_err()
@@ -182,7 +177,9 @@ mod tests {
     #[test]
     fn test_regex_flags_do_not_eat_next_chars() {
         let mut chars = "/foo/g.".into_code_iterator("script.js".to_string());
-        let result = try_parse_regex_literal(&mut chars, None).unwrap().unwrap();
+        let result = try_parse_regex_literal(&mut chars, GoalSymbol::ExpectRegex)
+            .unwrap()
+            .unwrap();
         assert_eq!(
             result,
             RegexLiteral {